@@ -0,0 +1,42 @@
+#![feature(test)]
+
+extern crate num_bigint_dig as num_bigint;
+extern crate num_traits;
+extern crate test;
+
+use num_bigint::BigUint;
+use num_traits::One;
+use test::Bencher;
+
+/// Build two large operands of roughly `n` limbs via a Fibonacci recurrence,
+/// so the subtraction hot path runs over long, same-length slices.
+fn fib_pair(n: usize) -> (BigUint, BigUint) {
+    let mut f0 = BigUint::one();
+    let mut f1 = BigUint::one();
+    while f1.bits() < n * 64 {
+        let next = &f1 + &f0;
+        f0 = f1;
+        f1 = next;
+    }
+    (f1, f0)
+}
+
+#[bench]
+fn sub_large(b: &mut Bencher) {
+    let (x, y) = fib_pair(256);
+    b.iter(|| &x - &y);
+}
+
+#[bench]
+fn sub_large_iter(b: &mut Bencher) {
+    let (mut f1, mut f0) = fib_pair(256);
+    b.iter(|| {
+        // Fibonacci-style alternation: repeatedly subtract the smaller operand.
+        let next = &f1 - &f0;
+        f1 = f0.clone();
+        f0 = next;
+        if f0 > f1 {
+            std::mem::swap(&mut f0, &mut f1);
+        }
+    });
+}
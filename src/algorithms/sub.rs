@@ -5,13 +5,43 @@ use num_traits::Zero;
 use smallvec::SmallVec;
 
 use crate::algorithms::cmp_slice;
-use crate::big_digit::{BigDigit, SignedDoubleBigDigit, BITS};
+use crate::big_digit::{BigDigit, SignedDoubleBigDigit};
 use crate::bigint::Sign::{self, *};
 use crate::{BigUint, VEC_SIZE};
 
-/// Subtract with borrow:
+/// Subtract with borrow.
+///
+/// `acc` threads the borrow between successive calls and is left non-zero iff
+/// the subtraction borrowed, so a final `*acc != 0` test still distinguishes an
+/// underflow. The x86-64 variants carry only the hardware borrow flag (`0`/`1`)
+/// in `acc` and compile to a straight `sbb`/`sub` chain via the `_subborrow`
+/// intrinsics; the portable fallback instead threads the full signed
+/// accumulator (`0`/`-1`) as before.
+#[cfg(all(target_arch = "x86_64", u64_digit))]
 #[inline]
 pub fn sbb(a: BigDigit, b: BigDigit, acc: &mut SignedDoubleBigDigit) -> BigDigit {
+    let mut out = 0;
+    let borrow = unsafe { core::arch::x86_64::_subborrow_u64((*acc & 1) as u8, a, b, &mut out) };
+    *acc = borrow as SignedDoubleBigDigit;
+    out
+}
+
+/// Subtract with borrow (x86-64, 32-bit limbs). See the 64-bit variant.
+#[cfg(all(target_arch = "x86_64", not(u64_digit)))]
+#[inline]
+pub fn sbb(a: BigDigit, b: BigDigit, acc: &mut SignedDoubleBigDigit) -> BigDigit {
+    let mut out = 0;
+    let borrow = unsafe { core::arch::x86_64::_subborrow_u32((*acc & 1) as u8, a, b, &mut out) };
+    *acc = borrow as SignedDoubleBigDigit;
+    out
+}
+
+/// Subtract with borrow (portable software fallback).
+#[cfg(not(target_arch = "x86_64"))]
+#[inline]
+pub fn sbb(a: BigDigit, b: BigDigit, acc: &mut SignedDoubleBigDigit) -> BigDigit {
+    use crate::big_digit::BITS;
+
     *acc += a as SignedDoubleBigDigit;
     *acc -= b as SignedDoubleBigDigit;
     let lo = *acc as BigDigit;
@@ -30,8 +60,6 @@ fn is_zero(x: &[BigDigit]) -> bool {
 
 #[inline]
 pub fn __sub2(a: &mut [BigDigit], b: &[BigDigit]) -> bool {
-    let mut borrow = 0;
-
     if b.len() == 1 {
         return __sub_scalar(a, b[0]);
     }
@@ -41,15 +69,26 @@ pub fn __sub2(a: &mut [BigDigit], b: &[BigDigit]) -> bool {
 
     let len = cmp::min(a.len(), b.len());
     let (a_lo, a_hi) = a.split_at_mut(len);
-    let (b_lo, b_hi) = b.split_at(len);
+    let (b_lo, _b_hi) = b.split_at(len);
 
-    for (a, b) in a_lo.iter_mut().zip(b_lo) {
-        *a = sbb(*a, *b, &mut borrow);
+    // Main loop: subtract limb-by-limb, carrying the borrow as a plain 0/1
+    // rather than a wide signed accumulator. Using `overflowing_sub` twice
+    // removes the `>>= BITS` shift; the borrow still flows limb to limb.
+    let mut borrow = 0;
+    for (ai, bi) in a_lo.iter_mut().zip(b_lo) {
+        let (d, b1) = ai.overflowing_sub(*bi);
+        let (d, b2) = d.overflowing_sub(borrow);
+        *ai = d;
+        borrow = (b1 | b2) as BigDigit;
     }
 
+    // Propagate the remaining borrow through the high limbs, exiting as soon
+    // as it clears (cf. `__sub_scalar`).
     if borrow != 0 {
-        for a in a_hi {
-            *a = sbb(*a, 0, &mut borrow);
+        for ai in a_hi {
+            let (d, b1) = ai.overflowing_sub(borrow);
+            *ai = d;
+            borrow = b1 as BigDigit;
             if borrow == 0 {
                 break;
             }
@@ -141,11 +180,85 @@ pub fn sub_sign(a: &[BigDigit], b: &[BigDigit]) -> (Sign, BigUint) {
     }
 }
 
+impl BigUint {
+    /// Subtract `other` from `self`, returning `None` on underflow.
+    #[inline]
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        // `__sub2` only spans `min(len)` limbs, so guard on the full magnitude
+        // before subtracting — otherwise `other`'s high limbs are ignored.
+        if cmp_slice(&self.data, &other.data) == Less {
+            return None;
+        }
+
+        let mut a: SmallVec<[BigDigit; VEC_SIZE]> = self.data[..].into();
+        if __sub2(&mut a, &other.data) {
+            None
+        } else {
+            Some(BigUint::new_native(a))
+        }
+    }
+
+    /// Subtract `other` from `self`, clamping to zero on underflow.
+    #[inline]
+    pub fn saturating_sub(&self, other: &BigUint) -> BigUint {
+        if cmp_slice(&self.data, &other.data) == Less {
+            return Zero::zero();
+        }
+
+        let mut a: SmallVec<[BigDigit; VEC_SIZE]> = self.data[..].into();
+        if __sub2(&mut a, &other.data) {
+            Zero::zero()
+        } else {
+            BigUint::new_native(a)
+        }
+    }
+
+    /// Subtract `other` from `self` modulo `base^limbs`, returning the
+    /// two's-complement (wrapping) result in a `limbs`-wide buffer.
+    #[inline]
+    pub fn wrapping_sub_within(&self, other: &BigUint, limbs: usize) -> BigUint {
+        let mut a: SmallVec<[BigDigit; VEC_SIZE]> = SmallVec::with_capacity(limbs);
+        a.extend(self.data.iter().take(limbs).cloned());
+        a.resize(limbs, 0);
+
+        // Subtract `other` (zero-extended to `limbs`) limb by limb, discarding
+        // the borrow out of the top limb — that is the `mod base^limbs` wrap.
+        // We don't route this through `__sub2` because it short-circuits on an
+        // all-zero minuend without writing the two's-complement result.
+        let mut borrow = 0;
+        for (i, ai) in a.iter_mut().enumerate() {
+            let bi = other.data.get(i).copied().unwrap_or(0);
+            let (d, b1) = ai.overflowing_sub(bi);
+            let (d, b2) = d.overflowing_sub(borrow);
+            *ai = d;
+            borrow = (b1 | b2) as BigDigit;
+        }
+
+        BigUint::new_native(a)
+    }
+
+    /// Compute `lhs - self`, reusing `self`'s allocation for the result.
+    ///
+    /// `self` plays the role of the right-hand operand; its buffer is extended
+    /// in place when `lhs` has more limbs, so no fresh allocation is needed
+    /// when the existing buffer is already large enough. Fails on underflow.
+    #[inline]
+    pub fn sub_from(mut self, lhs: &BigUint) -> BigUint {
+        if self.data.len() < lhs.data.len() {
+            self.data.resize(lhs.data.len(), 0);
+        }
+
+        sub2rev(&lhs.data, &mut self.data);
+
+        BigUint::new_native(self.data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use num_traits::Num;
+    use num_traits::{Num, One};
 
     use crate::BigInt;
 
@@ -164,4 +277,54 @@ mod tests {
         assert_eq!(sub_sign_i(&a.data[..], &b.data[..]), &a_i - &b_i);
         assert_eq!(sub_sign_i(&b.data[..], &a.data[..]), &b_i - &a_i);
     }
+
+    #[test]
+    fn test_non_panicking_sub() {
+        let a = BigUint::from_str_radix("265252859812191058636308480000000", 10).unwrap();
+        let b = BigUint::from_str_radix("26525285981219105863630848000000", 10).unwrap();
+
+        assert_eq!(a.checked_sub(&b), Some(&a - &b));
+        assert_eq!(b.checked_sub(&a), None);
+
+        assert_eq!(a.saturating_sub(&b), &a - &b);
+        assert_eq!(b.saturating_sub(&a), Zero::zero());
+
+        // `other` wider than `self`: must underflow, not ignore the high limbs.
+        let one = BigUint::one();
+        let wide = BigUint::one() << crate::big_digit::BITS;
+        assert_eq!(one.checked_sub(&wide), None);
+        assert_eq!(one.saturating_sub(&wide), Zero::zero());
+
+        // Without underflow, wrapping matches ordinary subtraction.
+        assert_eq!(a.wrapping_sub_within(&b, a.data.len()), &a - &b);
+
+        // On underflow, `wrapped + (a - b) == base^limbs`.
+        let limbs = a.data.len() + 1;
+        let wrapped = b.wrapping_sub_within(&a, limbs);
+        let modulus = BigUint::one() << (limbs * crate::big_digit::BITS);
+        assert_eq!(wrapped + (&a - &b), modulus);
+
+        // Zero minuend: `0 - other` must wrap to `base^limbs - other`, not `0`.
+        let zero: BigUint = Zero::zero();
+        assert_eq!(zero.wrapping_sub_within(&a, limbs) + &a, modulus);
+        assert_eq!(zero.wrapping_sub_within(&zero, limbs), zero);
+    }
+
+    #[test]
+    fn test_sub_from() {
+        let a = BigUint::from_str_radix("265252859812191058636308480000000", 10).unwrap();
+        let b = BigUint::from_str_radix("26525285981219105863630848000000", 10).unwrap();
+
+        // Owned RHS reuses `b`'s buffer (same limb count as `a`) for the result.
+        assert_eq!(b.clone().sub_from(&a), a.checked_sub(&b).unwrap());
+        assert_eq!(a.clone().sub_from(&a), Zero::zero());
+
+        // Result carries trailing zero limbs (operands differ only in low limbs):
+        // `sub_from` must normalize them away via `new_native`.
+        let hi = BigUint::one() << (4 * crate::big_digit::BITS);
+        let x = &hi + BigUint::from(7u32);
+        let y = &hi + BigUint::from(3u32);
+        // `x - y == 4`: the shared high limbs cancel, leaving trailing zeros.
+        assert_eq!(y.clone().sub_from(&x), BigUint::from(4u32));
+    }
 }